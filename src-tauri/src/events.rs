@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Listener, Manager};
+
+use crate::jup::TokenSymbol;
+use crate::price_target::{AlertDirection, PriceTarget};
+use crate::{persistence, AppState};
+
+pub const EVENT_WATCHLIST_ADD: &str = "watchlist:add";
+pub const EVENT_WATCHLIST_REMOVE: &str = "watchlist:remove";
+pub const EVENT_TARGET_SET: &str = "target:set";
+pub const EVENT_SELECT: &str = "select";
+pub const EVENT_PRICE_UPDATE: &str = "price:update";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WatchlistAddPayload {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WatchlistRemovePayload {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TargetSetPayload {
+    pub token_or_pair_symbol: String,
+    pub price: f64,
+    pub direction: AlertDirection,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelectPayload {
+    pub symbols: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PriceUpdatePayload {
+    pub symbol: String,
+    pub price: Option<f64>,
+}
+
+/// Hooks up the Settings webview's event protocol to `AppState`. `watchlist:add`,
+/// `watchlist:remove`, `target:set`, and `select` each mutate state the same
+/// way a tray menu click or Tauri command would, then persist and push the
+/// current watch set into the poller so the UI never needs a restart.
+pub fn register_listeners(app_handle: &AppHandle) {
+    {
+        let handle = app_handle.clone();
+        app_handle.listen(EVENT_WATCHLIST_ADD, move |event| {
+            let Ok(payload) = serde_json::from_str::<WatchlistAddPayload>(event.payload()) else {
+                return;
+            };
+            add_watch(&handle, payload.symbol);
+        });
+    }
+
+    {
+        let handle = app_handle.clone();
+        app_handle.listen(EVENT_WATCHLIST_REMOVE, move |event| {
+            let Ok(payload) = serde_json::from_str::<WatchlistRemovePayload>(event.payload())
+            else {
+                return;
+            };
+            remove_watch(&handle, &payload.symbol);
+        });
+    }
+
+    {
+        let handle = app_handle.clone();
+        app_handle.listen(EVENT_TARGET_SET, move |event| {
+            let Ok(payload) = serde_json::from_str::<TargetSetPayload>(event.payload()) else {
+                return;
+            };
+            set_target(&handle, payload);
+        });
+    }
+
+    {
+        let handle = app_handle.clone();
+        app_handle.listen(EVENT_SELECT, move |event| {
+            let Ok(payload) = serde_json::from_str::<SelectPayload>(event.payload()) else {
+                return;
+            };
+            select(&handle, payload.symbols);
+        });
+    }
+}
+
+/// Emits a `price:update` event so an open Settings window can show a live
+/// in-window price without polling the tray state itself.
+pub fn emit_price_update(app_handle: &AppHandle, symbol: &str, price: Option<f64>) {
+    let _ = app_handle.emit(
+        EVENT_PRICE_UPDATE,
+        PriceUpdatePayload {
+            symbol: symbol.to_string(),
+            price,
+        },
+    );
+}
+
+fn add_watch(app_handle: &AppHandle, symbol: String) {
+    let state = app_handle.state::<AppState>();
+
+    {
+        let mut price_watches = state.price_watches.lock().unwrap();
+        if !price_watches.contains(&symbol) {
+            price_watches.push(symbol);
+        }
+    }
+
+    sync_watch_targets(app_handle, &state);
+    let _ = persistence::save_from_state(app_handle, &state);
+}
+
+fn remove_watch(app_handle: &AppHandle, symbol: &str) {
+    let state = app_handle.state::<AppState>();
+
+    {
+        let mut price_watches = state.price_watches.lock().unwrap();
+        price_watches.retain(|existing| existing != symbol);
+    }
+
+    sync_watch_targets(app_handle, &state);
+    let _ = persistence::save_from_state(app_handle, &state);
+}
+
+fn set_target(app_handle: &AppHandle, payload: TargetSetPayload) {
+    let state = app_handle.state::<AppState>();
+
+    {
+        let mut price_targets = state.price_targets.lock().unwrap();
+        price_targets
+            .retain(|target| target.token_or_pair_symbol != payload.token_or_pair_symbol);
+        price_targets.push(PriceTarget::new(
+            payload.token_or_pair_symbol.clone(),
+            payload.price,
+            payload.direction,
+        ));
+
+        let mut price_watches = state.price_watches.lock().unwrap();
+        if !price_watches.contains(&payload.token_or_pair_symbol) {
+            price_watches.push(payload.token_or_pair_symbol);
+        }
+    }
+
+    // Mirror `add_price_target`: a target set through the Settings bridge is
+    // only ever evaluated if its symbol is in the polled watch set, so push
+    // the updated set immediately rather than leaving it inert until restart.
+    sync_watch_targets(app_handle, &state);
+    let _ = persistence::save_from_state(app_handle, &state);
+}
+
+fn select(app_handle: &AppHandle, symbols: Vec<String>) {
+    let state = app_handle.state::<AppState>();
+    let registry = state.token_registry.lock().unwrap().clone();
+
+    let parsed_symbols: Vec<TokenSymbol> = symbols
+        .iter()
+        .filter_map(|symbol| symbol.parse::<TokenSymbol>().ok())
+        .collect();
+
+    let tokens: Vec<_> = parsed_symbols
+        .iter()
+        .filter_map(|symbol| registry.get_by_symbol(symbol).cloned())
+        .collect();
+
+    if tokens.is_empty() {
+        return;
+    }
+
+    *state.selected_tokens.lock().unwrap() = tokens;
+
+    // `token_sender`'s receiver was dropped when `run_loop` was redesigned to
+    // consume `watch_receiver` instead, so sending on it is a no-op. Add the
+    // selection to the watch set instead, the same way the poller actually
+    // learns about new assets.
+    let watch_symbol = parsed_symbols
+        .iter()
+        .map(|symbol| symbol.to_string())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    {
+        let mut price_watches = state.price_watches.lock().unwrap();
+        if !price_watches.contains(&watch_symbol) {
+            price_watches.push(watch_symbol);
+        }
+    }
+
+    sync_watch_targets(app_handle, &state);
+    let _ = persistence::save_from_state(app_handle, &state);
+}
+
+/// Re-resolves `price_watches` into `WatchTarget`s and pushes them to the
+/// poller, also refreshing the address -> symbol map the alert engine needs.
+/// Shared with the `add_price_target`/`remove_price_target` commands so a
+/// target or watch added at runtime is polled immediately, not after restart.
+pub(crate) fn sync_watch_targets(app_handle: &AppHandle, state: &AppState) {
+    let registry = state.token_registry.lock().unwrap().clone();
+    let price_watches = state.price_watches.lock().unwrap().clone();
+
+    let (targets, watch_symbols) = crate::runner::resolve_watch_targets(&registry, &price_watches);
+    *state.watch_symbols.lock().unwrap() = watch_symbols;
+
+    if let Some(sender) = state.watch_sender.lock().unwrap().as_ref() {
+        let _ = sender.send(targets);
+    }
+}
@@ -1,9 +1,13 @@
 pub mod assets;
 pub mod commands;
+pub mod events;
 pub mod feeder;
 pub mod fetcher;
 pub mod formatter;
 pub mod jup;
+pub mod persistence;
+pub mod price_source;
+pub mod price_target;
 pub mod ray;
 pub mod runner;
 pub mod token_registry;
@@ -11,11 +15,13 @@ pub mod tray;
 
 use chrono::Local;
 use commands::core::{greet, update_token_and_price};
-use feeder::{PairOrTokenAddress, PairOrTokenPriceInfo};
+use feeder::{PairOrTokenAddress, PriceInfo};
 use formatter::update_price_display;
 use jup::TokenSymbol;
 use log::LevelFilter;
-use runner::run_loop;
+use price_target::{add_price_target, check_and_notify, remove_price_target, PriceTarget};
+use ray::{set_pool_timeframe, PoolId, PoolStats, TimeFrame};
+use runner::{run_loop, WatchTarget};
 use std::io::Write;
 
 use tauri::{
@@ -24,17 +30,11 @@ use tauri::{
 };
 use token_registry::{Token, TokenRegistry};
 use tokio::sync::watch;
+use tokio::time::{sleep, Duration};
 use tray::setup_tray;
 
 use std::{collections::HashMap, sync::Mutex};
 
-#[allow(dead_code)]
-#[derive(Clone)]
-pub struct PriceTarget {
-    token_or_pair_symbol: String,
-    price: f64,
-}
-
 #[derive(Default)]
 pub struct AppState {
     tray_id: Mutex<Option<TrayIconId>>,
@@ -45,6 +45,14 @@ pub struct AppState {
     is_quit: Mutex<bool>,
     price_targets: Mutex<Vec<PriceTarget>>,
     price_watches: Mutex<Vec<String>>,
+    /// Last price seen per `token_or_pair_symbol`, used to detect target crossings.
+    prev_prices: Mutex<HashMap<String, f64>>,
+    watch_sender: Mutex<Option<watch::Sender<Vec<WatchTarget>>>>,
+    /// `WatchTarget::key() -> price_watches symbol`, kept in sync with `watch_sender`.
+    watch_symbols: Mutex<HashMap<String, String>>,
+    /// Latest Raydium pool analytics per watch-target key, refreshed on a timer.
+    pool_stats: Mutex<HashMap<String, PoolStats>>,
+    pool_timeframe: Mutex<TimeFrame>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -75,42 +83,59 @@ pub fn run() {
             *app_state.tray_id.lock().unwrap() = Some(tray_id.clone());
             *app_state.tray_menu.lock().unwrap() = Some(tray_menu.clone());
 
-            let (token_sender, token_receiver) = watch::channel(vec![TokenRegistry::new()
+            let (token_sender, _token_receiver) = watch::channel(vec![TokenRegistry::new()
                 .get_by_symbol(&TokenSymbol::SOL)
                 .expect("Token ot exist")
                 .clone()]);
             *app_state.token_sender.lock().unwrap() = Some(token_sender);
 
-            let (price_sender, price_receiver) = watch::channel::<
-                HashMap<PairOrTokenAddress, PairOrTokenPriceInfo>,
-            >(Default::default());
+            let (price_sender, price_receiver) =
+                watch::channel::<HashMap<PairOrTokenAddress, PriceInfo>>(Default::default());
             let app_handle = app.handle().clone();
 
-            // Default to SOL
-            let selected_token = token_registry
-                .get_by_symbol(&TokenSymbol::SOL)
-                .expect("Invalid token")
-                .clone();
-            *app_state.selected_tokens.lock().unwrap() = vec![selected_token];
-
-            // Test
-            let sol_symbol = TokenSymbol::SOL.to_string();
-            let pair_symbol = format!("{}_{}", TokenSymbol::JLP, TokenSymbol::SOL);
-            let price_targets = vec![
-                PriceTarget {
-                    token_or_pair_symbol: sol_symbol.clone(),
-                    price: 200f64,
-                },
-                PriceTarget {
-                    token_or_pair_symbol: pair_symbol.clone(),
-                    price: 0.021f64,
-                },
-            ];
-            *app_state.price_targets.lock().unwrap() = price_targets.clone();
-
-            let price_watches = vec![sol_symbol, pair_symbol];
+            let stored_config = persistence::load(app.handle()).unwrap_or_else(|e| {
+                eprintln!("Failed to load persisted config, using defaults: {e}");
+                None
+            });
+
+            let (selected_tokens, price_watches, price_targets) = match stored_config {
+                Some(config) => (
+                    config.selected_tokens,
+                    config.price_watches,
+                    config.price_targets.iter().map(PriceTarget::from).collect(),
+                ),
+                None => {
+                    // Default to SOL
+                    let selected_token = token_registry
+                        .get_by_symbol(&TokenSymbol::SOL)
+                        .expect("Invalid token")
+                        .clone();
+
+                    let sol_symbol = TokenSymbol::SOL.to_string();
+                    let pair_symbol = format!("{}_{}", TokenSymbol::JLP, TokenSymbol::SOL);
+                    let price_targets = vec![
+                        PriceTarget::new(sol_symbol.clone(), 200f64, price_target::AlertDirection::Either),
+                        PriceTarget::new(pair_symbol.clone(), 0.021f64, price_target::AlertDirection::Either),
+                    ];
+
+                    (vec![selected_token], vec![sol_symbol, pair_symbol], price_targets)
+                }
+            };
+
+            *app_state.selected_tokens.lock().unwrap() = selected_tokens;
+            *app_state.price_targets.lock().unwrap() = price_targets;
             *app_state.price_watches.lock().unwrap() = price_watches.clone();
 
+            let (watch_targets, watch_symbols) =
+                runner::resolve_watch_targets(&token_registry, &price_watches);
+            *app_state.watch_symbols.lock().unwrap() = watch_symbols;
+            let (watch_sender, watch_receiver) = watch::channel(watch_targets);
+            *app_state.watch_sender.lock().unwrap() = Some(watch_sender);
+
+            if let Err(e) = persistence::save_from_state(app.handle(), &app_state) {
+                eprintln!("Failed to persist initial config: {e}");
+            }
+
             let selected_tokens = app_state.selected_tokens.lock().unwrap().clone();
 
             let tray_menu = app_state
@@ -121,6 +146,9 @@ pub fn run() {
                 .expect("Tray not initialized");
 
             let tray_icon = app_handle.tray_by_id(&tray_id).expect("Tray missing");
+            let alert_app_handle = app_handle.clone();
+
+            events::register_listeners(app.handle());
 
             // Price effect
             tauri::async_runtime::spawn(async move {
@@ -148,6 +176,9 @@ pub fn run() {
 
                     // Update menu
                     let items = tray_menu.items().unwrap();
+                    let state = alert_app_handle.state::<AppState>();
+                    let pool_stats = state.pool_stats.lock().unwrap();
+                    let pool_timeframe = *state.pool_timeframe.lock().unwrap();
                     price_info_map.iter().for_each(|(k, v)| {
                         if let Some(item) = items
                             .iter()
@@ -155,27 +186,90 @@ pub fn run() {
                         {
                             if let Some(item) = item.as_icon_menuitem() {
                                 let (_label, formatted_price) = update_price_display(v);
-                                let _ = item.set_text(formatted_price);
+                                let text = match pool_stats.get(k) {
+                                    Some(stats) => format!(
+                                        "{formatted_price} | APR {:.1}% TVL ${:.0}k {} Vol ${:.0}k",
+                                        stats.apr * 100.0,
+                                        stats.tvl / 1_000.0,
+                                        pool_timeframe.label(),
+                                        stats.volume / 1_000.0
+                                    ),
+                                    None => formatted_price,
+                                };
+                                let _ = item.set_text(text);
                             }
                         }
                     });
+                    drop(pool_stats);
+
+                    // Fire price-target alerts on threshold crossings, and push a
+                    // live update to any open Settings window. `price_info_map` is
+                    // keyed by mint address, so it's resolved back to the symbol
+                    // `price_targets` are keyed by via `watch_symbols`.
+                    let watch_symbols = state.watch_symbols.lock().unwrap();
+                    price_info_map.iter().for_each(|(k, v)| {
+                        let Some(symbol) = watch_symbols.get(k) else {
+                            return;
+                        };
+
+                        events::emit_price_update(&alert_app_handle, symbol, v.price);
+
+                        let Some(price) = v.price else {
+                            return;
+                        };
+
+                        let prev = {
+                            let mut prev_prices = state.prev_prices.lock().unwrap();
+                            prev_prices.insert(symbol.clone(), price).unwrap_or(price)
+                        };
+
+                        check_and_notify(&alert_app_handle, symbol, prev, price);
+                    });
+                    drop(watch_symbols);
                 }
             });
 
-            // // Notify
-            // app.notification()
-            // .builder()
-            // .title(format!(
-            //     "{}: ${}",
-            //     price_target.token_or_pair_symbol,
-            //     format_price(price)
-            // ))
-            // // .body(format!("${}", format_price(price)))
-            // .show()
-            // .unwrap();
+            // Periodic Raydium pool-stats fetch for watched pairs that map to a pool.
+            {
+                let pool_app_handle = app_handle.clone();
+                let mut pool_watch_receiver = watch_receiver.clone();
+
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        let targets = pool_watch_receiver.borrow_and_update().clone();
+                        let state = pool_app_handle.state::<AppState>();
+                        let timeframe = *state.pool_timeframe.lock().unwrap();
+
+                        for target in &targets {
+                            let WatchTarget::Pair(a, b) = target else {
+                                continue;
+                            };
+
+                            let Some(pool_id) = PoolId::for_mints(&a.address, &b.address) else {
+                                continue;
+                            };
+
+                            match ray::fetch_pool_info_by_id(pool_id).await {
+                                Ok(pool_data) => {
+                                    state
+                                        .pool_stats
+                                        .lock()
+                                        .unwrap()
+                                        .insert(target.key(), pool_data.stats(timeframe));
+                                }
+                                Err(e) => {
+                                    eprintln!("Pool stats fetch failed for {}: {}", target.key(), e)
+                                }
+                            }
+                        }
+
+                        sleep(Duration::from_secs(60)).await;
+                    }
+                });
+            }
 
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = run_loop(price_sender, token_receiver).await {
+                if let Err(e) = run_loop(price_sender, watch_receiver).await {
                     eprintln!("Price fetch error: {}", e);
                 }
             });
@@ -243,14 +337,26 @@ pub fn run() {
 
                     tauri::async_runtime::spawn(async move {
                         // Spawn a new async task
-                        if let Err(e) = update_token_and_price(app_handle, tokens).await {
-                            eprintln!("Error updating token and price: {}", e);
+                        match update_token_and_price(app_handle.clone(), tokens).await {
+                            Ok(_) => {
+                                let state = app_handle.state::<AppState>();
+                                if let Err(e) = persistence::save_from_state(&app_handle, &state) {
+                                    eprintln!("Failed to persist selected tokens: {e}");
+                                }
+                            }
+                            Err(e) => eprintln!("Error updating token and price: {}", e),
                         }
                     });
                 }
             }
         })
-        .invoke_handler(tauri::generate_handler![greet, update_token_and_price])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            update_token_and_price,
+            add_price_target,
+            remove_price_target,
+            set_pool_timeframe
+        ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+use crate::price_target::{AlertDirection, PriceTarget};
+use crate::ray::TimeFrame;
+use crate::token_registry::Token;
+use crate::AppState;
+
+const CONFIG_FILE_NAME: &str = "config.json";
+const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredPriceTarget {
+    pub token_or_pair_symbol: String,
+    pub price: f64,
+    pub direction: AlertDirection,
+}
+
+impl From<&PriceTarget> for StoredPriceTarget {
+    fn from(target: &PriceTarget) -> Self {
+        Self {
+            token_or_pair_symbol: target.token_or_pair_symbol.clone(),
+            price: target.price,
+            direction: target.direction,
+        }
+    }
+}
+
+impl From<&StoredPriceTarget> for PriceTarget {
+    fn from(stored: &StoredPriceTarget) -> Self {
+        PriceTarget::new(
+            stored.token_or_pair_symbol.clone(),
+            stored.price,
+            stored.direction,
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Config {
+    pub schema_version: u32,
+    pub selected_tokens: Vec<Token>,
+    pub price_watches: Vec<String>,
+    pub price_targets: Vec<StoredPriceTarget>,
+    pub pool_timeframe: TimeFrame,
+}
+
+fn config_path(app_handle: &AppHandle) -> Result<PathBuf> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .context("resolving app config dir")?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(CONFIG_FILE_NAME))
+}
+
+/// Loads the persisted config, migrating it forward to `CURRENT_SCHEMA_VERSION`
+/// if it was written by an older build. Returns `None` if no config exists yet.
+pub fn load(app_handle: &AppHandle) -> Result<Option<Config>> {
+    let path = config_path(app_handle)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(&path)?;
+    let value: serde_json::Value = serde_json::from_str(&raw)?;
+    Ok(Some(migrate(value)?))
+}
+
+/// Rewrites the config file from the app's current state. Called whenever a
+/// menu event or command mutates selected tokens, watches, or targets.
+pub fn save_from_state(app_handle: &AppHandle, state: &AppState) -> Result<()> {
+    let config = Config {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        selected_tokens: state.selected_tokens.lock().unwrap().clone(),
+        price_watches: state.price_watches.lock().unwrap().clone(),
+        price_targets: state
+            .price_targets
+            .lock()
+            .unwrap()
+            .iter()
+            .map(StoredPriceTarget::from)
+            .collect(),
+        pool_timeframe: *state.pool_timeframe.lock().unwrap(),
+    };
+
+    let path = config_path(app_handle)?;
+    fs::write(&path, serde_json::to_string_pretty(&config)?)?;
+    Ok(())
+}
+
+/// Upgrades an on-disk config `serde_json::Value` written by an older
+/// schema version to the current shape.
+fn migrate(mut value: serde_json::Value) -> Result<Config> {
+    let version = value
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    if version == 0 {
+        if let Some(config) = value.as_object_mut() {
+            // Pre-schema configs predate price targets entirely.
+            config
+                .entry("price_targets")
+                .or_insert_with(|| json!([]));
+            config.insert("schema_version".to_string(), json!(1));
+        }
+    }
+
+    if version < 2 {
+        if let Some(config) = value.as_object_mut() {
+            // Pool analytics timeframe didn't exist before v2; default to daily.
+            config
+                .entry("pool_timeframe")
+                .or_insert_with(|| json!("day"));
+            config.insert("schema_version".to_string(), json!(CURRENT_SCHEMA_VERSION));
+        }
+    }
+
+    // `direction` was added to stored price targets after the array itself,
+    // so a v1 (or hand-edited) config can still be missing it on individual
+    // entries. Backfill rather than gate on version, since that's the only
+    // thing that actually guarantees old configs deserialize cleanly.
+    if let Some(targets) = value
+        .get_mut("price_targets")
+        .and_then(|v| v.as_array_mut())
+    {
+        for target in targets {
+            if let Some(target) = target.as_object_mut() {
+                target
+                    .entry("direction")
+                    .or_insert_with(|| json!("either"));
+            }
+        }
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
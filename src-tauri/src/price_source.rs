@@ -0,0 +1,124 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use crate::jup::{fetch_pair_price, fetch_price};
+use crate::ray::{fetch_pool_info_by_id, PoolId};
+
+/// A venue that can quote a mint or a mint pair. Implemented once per
+/// aggregator/AMM so `run_loop` can fall back across venues instead of
+/// hard-coding a single one.
+#[async_trait]
+pub trait PriceSource {
+    fn name(&self) -> &'static str;
+
+    /// Whether this source is expected to have a quote for `mint` at all,
+    /// checked before spending a request on it.
+    fn supports(&self, mint: &str) -> bool;
+
+    async fn fetch_price(&self, mint: &str) -> Result<f64>;
+    async fn fetch_pair_price(&self, a: &str, b: &str) -> Result<f64>;
+}
+
+pub struct JupSource;
+
+#[async_trait]
+impl PriceSource for JupSource {
+    fn name(&self) -> &'static str {
+        "jupiter"
+    }
+
+    fn supports(&self, _mint: &str) -> bool {
+        true
+    }
+
+    async fn fetch_price(&self, mint: &str) -> Result<f64> {
+        fetch_price(mint).await
+    }
+
+    async fn fetch_pair_price(&self, a: &str, b: &str) -> Result<f64> {
+        fetch_pair_price(a, b).await
+    }
+}
+
+pub struct RaySource;
+
+#[async_trait]
+impl PriceSource for RaySource {
+    fn name(&self) -> &'static str {
+        "raydium"
+    }
+
+    fn supports(&self, mint: &str) -> bool {
+        PoolId::supports_mint(mint)
+    }
+
+    async fn fetch_price(&self, _mint: &str) -> Result<f64> {
+        Err(anyhow!("raydium source only quotes pool pairs, not single mints"))
+    }
+
+    async fn fetch_pair_price(&self, a: &str, b: &str) -> Result<f64> {
+        let pool_id = PoolId::for_mints(a, b)
+            .ok_or_else(|| anyhow!("no raydium pool tracked for mint pair {a}/{b}"))?;
+
+        Ok(fetch_pool_info_by_id(pool_id).await?.price)
+    }
+}
+
+/// A quote is only worth accepting if it's a real, positive price. Neither
+/// venue's plain `f64` response carries a timestamp to check for staleness
+/// against, so this is the staleness/empty-quote gate the fallback can
+/// actually implement: a non-finite or non-positive price is treated the
+/// same as a hard error and the registry moves on to the next source.
+fn is_fresh_quote(price: f64) -> bool {
+    price.is_finite() && price > 0.0
+}
+
+/// Tries each source in priority order, falling back to the next one if
+/// the current source doesn't support the mint, errors, or comes back with
+/// a stale/empty quote.
+pub struct PriceSourceRegistry {
+    sources: Vec<Box<dyn PriceSource + Send + Sync>>,
+}
+
+impl PriceSourceRegistry {
+    pub fn new(sources: Vec<Box<dyn PriceSource + Send + Sync>>) -> Self {
+        Self { sources }
+    }
+
+    /// The default fallback order: Jupiter first, Raydium pool price second.
+    pub fn default_order() -> Self {
+        Self::new(vec![Box::new(JupSource), Box::new(RaySource)])
+    }
+
+    pub async fn fetch_price(&self, mint: &str) -> Result<f64> {
+        let mut last_err = anyhow!("no price source configured");
+
+        for source in &self.sources {
+            if !source.supports(mint) {
+                continue;
+            }
+
+            match source.fetch_price(mint).await {
+                Ok(price) if is_fresh_quote(price) => return Ok(price),
+                Ok(price) => last_err = anyhow!("{}: stale or empty quote ({price})", source.name()),
+                Err(e) => last_err = anyhow!("{}: {e}", source.name()),
+            }
+        }
+
+        Err(last_err)
+    }
+
+    pub async fn fetch_pair_price(&self, a: &str, b: &str) -> Result<f64> {
+        let mut last_err = anyhow!("no price source configured");
+
+        for source in &self.sources {
+            match source.fetch_pair_price(a, b).await {
+                Ok(price) if is_fresh_quote(price) => return Ok(price),
+                Ok(price) => last_err = anyhow!("{}: stale or empty quote ({price})", source.name()),
+                Err(e) => last_err = anyhow!("{}: {e}", source.name()),
+            }
+        }
+
+        Err(last_err)
+    }
+}
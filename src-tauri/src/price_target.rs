@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::AppState;
+
+/// Which way a price has to cross the target before we alert on it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertDirection {
+    Above,
+    Below,
+    Either,
+}
+
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60 * 15);
+
+#[derive(Clone)]
+pub struct PriceTarget {
+    pub(crate) token_or_pair_symbol: String,
+    pub(crate) price: f64,
+    pub(crate) direction: AlertDirection,
+    pub(crate) cooldown: Duration,
+    pub(crate) last_fired_at: Option<Instant>,
+}
+
+impl PriceTarget {
+    pub fn new(token_or_pair_symbol: String, price: f64, direction: AlertDirection) -> Self {
+        Self {
+            token_or_pair_symbol,
+            price,
+            direction,
+            cooldown: DEFAULT_COOLDOWN,
+            last_fired_at: None,
+        }
+    }
+
+    /// Returns true, and marks the target as fired, if `prev -> new` crosses
+    /// `self.price` in a direction this target cares about and the cooldown
+    /// has elapsed since the last time it fired.
+    fn try_fire(&mut self, prev: f64, new: f64) -> bool {
+        if !crossed(prev, new, self.price, self.direction) {
+            return false;
+        }
+
+        if let Some(last_fired_at) = self.last_fired_at {
+            if last_fired_at.elapsed() < self.cooldown {
+                return false;
+            }
+        }
+
+        self.last_fired_at = Some(Instant::now());
+        true
+    }
+}
+
+/// True when `(prev - target)` and `(new - target)` have different signs,
+/// i.e. the price actually crossed the target rather than merely hovering
+/// on one side of it, and the crossing matches `direction`.
+fn crossed(prev: f64, new: f64, target: f64, direction: AlertDirection) -> bool {
+    let prev_sign = (prev - target).signum();
+    let new_sign = (new - target).signum();
+
+    if new_sign == 0.0 || prev_sign == new_sign {
+        return false;
+    }
+
+    match direction {
+        AlertDirection::Above => new_sign > 0.0,
+        AlertDirection::Below => new_sign < 0.0,
+        AlertDirection::Either => true,
+    }
+}
+
+/// Checks every target for `symbol` against its previous and latest price,
+/// firing a native notification for each one that crosses.
+pub fn check_and_notify(app_handle: &AppHandle, symbol: &str, prev: f64, new: f64) {
+    let state = app_handle.state::<AppState>();
+    let mut price_targets = state.price_targets.lock().unwrap();
+
+    for target in price_targets
+        .iter_mut()
+        .filter(|target| target.token_or_pair_symbol == symbol)
+    {
+        if !target.try_fire(prev, new) {
+            continue;
+        }
+
+        let _ = app_handle
+            .notification()
+            .builder()
+            .title(format!("{} crossed ${}", symbol, target.price))
+            .body(format!("{} is now ${:.4}", symbol, new))
+            .show();
+    }
+}
+
+#[tauri::command]
+pub fn add_price_target(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    token_or_pair_symbol: String,
+    price: f64,
+    direction: AlertDirection,
+) {
+    {
+        let mut price_targets = state.price_targets.lock().unwrap();
+        price_targets.push(PriceTarget::new(token_or_pair_symbol.clone(), price, direction));
+
+        let mut price_watches = state.price_watches.lock().unwrap();
+        if !price_watches.contains(&token_or_pair_symbol) {
+            price_watches.push(token_or_pair_symbol);
+        }
+    }
+
+    // Re-resolve and push the updated watch set so a target added at runtime
+    // is polled immediately instead of only after the next restart.
+    crate::events::sync_watch_targets(&app_handle, &state);
+    let _ = crate::persistence::save_from_state(&app_handle, &state);
+}
+
+#[tauri::command]
+pub fn remove_price_target(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    token_or_pair_symbol: String,
+    price: f64,
+) {
+    {
+        let mut price_targets = state.price_targets.lock().unwrap();
+        price_targets.retain(|target| {
+            !(target.token_or_pair_symbol == token_or_pair_symbol && target.price == price)
+        });
+    }
+
+    let _ = crate::persistence::save_from_state(&app_handle, &state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_once_on_crossing_above() {
+        assert!(crossed(199.0, 201.0, 200.0, AlertDirection::Above));
+        assert!(!crossed(201.0, 202.0, 200.0, AlertDirection::Above));
+    }
+
+    #[test]
+    fn ignores_crossing_in_the_wrong_direction() {
+        assert!(!crossed(199.0, 201.0, 200.0, AlertDirection::Below));
+        assert!(crossed(201.0, 199.0, 200.0, AlertDirection::Below));
+    }
+
+    #[test]
+    fn either_direction_fires_both_ways() {
+        assert!(crossed(199.0, 201.0, 200.0, AlertDirection::Either));
+        assert!(crossed(201.0, 199.0, 200.0, AlertDirection::Either));
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let mut target = PriceTarget::new("SOL".to_string(), 200.0, AlertDirection::Either);
+        assert!(target.try_fire(199.0, 201.0));
+        // Still within cooldown, even though it re-crosses.
+        assert!(!target.try_fire(201.0, 199.0));
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use strum_macros::{Display, EnumString};
+use tauri::State;
+
+use crate::AppState;
 
 #[derive(EnumString, Display, Debug, Clone)]
 pub enum PoolId {
@@ -9,6 +12,57 @@ pub enum PoolId {
     SOL_JLP,
 }
 
+impl PoolId {
+    /// Mint pairs (in mint-address form) mapped to the pool that trades
+    /// them. Adding a new Raydium pool is a one-line addition here plus a
+    /// new `PoolId` variant.
+    fn mint_pair_registry() -> Vec<(String, String, PoolId)> {
+        use crate::jup::TokenId;
+
+        vec![(
+            TokenId::SOL.to_string(),
+            TokenId::JLP.to_string(),
+            PoolId::SOL_JLP,
+        )]
+    }
+
+    /// Resolves a pair of mint addresses (in either order) to the Raydium
+    /// pool that trades them, if one is tracked.
+    pub fn for_mints(a: &str, b: &str) -> Option<PoolId> {
+        Self::mint_pair_registry()
+            .into_iter()
+            .find(|(mint_a, mint_b, _)| {
+                (mint_a == a && mint_b == b) || (mint_a == b && mint_b == a)
+            })
+            .map(|(_, _, pool_id)| pool_id)
+    }
+
+    /// Whether `mint` is one side of any tracked pool.
+    pub fn supports_mint(mint: &str) -> bool {
+        Self::mint_pair_registry()
+            .into_iter()
+            .any(|(a, b, _)| a == mint || b == mint)
+    }
+}
+
+/// Which rolling window of `PoolData`'s stats to surface.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFrame {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+/// The subset of `PoolData` the tray cares about for a given `TimeFrame`.
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStats {
+    pub apr: f64,
+    pub tvl: f64,
+    pub volume: f64,
+}
+
 pub const RAYDIUM_BASE_API: &str = "https://api-v3.raydium.io";
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -93,12 +147,48 @@ async fn fetch_pool_info(url: &str) -> anyhow::Result<PoolInfoResponse> {
     Ok(json)
 }
 
-#[allow(dead_code)]
 pub async fn fetch_pool_info_by_id(id: PoolId) -> anyhow::Result<PoolData> {
     let pool_info =
-        fetch_pool_info(format!("{RAYDIUM_BASE_API}/pools/info/ids?ids={id}").as_str()).await;
+        fetch_pool_info(format!("{RAYDIUM_BASE_API}/pools/info/ids?ids={id}").as_str()).await?;
+
+    pool_info
+        .data
+        .first()
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("raydium returned no pool data for pool {id}"))
+}
+
+#[tauri::command]
+pub fn set_pool_timeframe(state: State<AppState>, timeframe: TimeFrame) {
+    *state.pool_timeframe.lock().unwrap() = timeframe;
+}
+
+impl TimeFrame {
+    /// Short label for the window this timeframe's stats cover, so tray text
+    /// doesn't keep saying "24h" after switching to `Week`/`Month`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeFrame::Day => "24h",
+            TimeFrame::Week => "7d",
+            TimeFrame::Month => "30d",
+        }
+    }
+}
 
-    Ok(pool_info?.data[0].clone())
+impl PoolData {
+    pub fn stats(&self, timeframe: TimeFrame) -> PoolStats {
+        let frame = match timeframe {
+            TimeFrame::Day => &self.day,
+            TimeFrame::Week => &self.week,
+            TimeFrame::Month => &self.month,
+        };
+
+        PoolStats {
+            apr: frame.apr,
+            tvl: self.tvl,
+            volume: frame.volume,
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -127,6 +217,18 @@ mod tests {
         assert!(price > 0.0);
     }
 
+    #[test]
+    fn test_pool_id_for_mints_either_order() {
+        use crate::jup::TokenId;
+
+        let sol = TokenId::SOL.to_string();
+        let jlp = TokenId::JLP.to_string();
+
+        assert!(matches!(PoolId::for_mints(&sol, &jlp), Some(PoolId::SOL_JLP)));
+        assert!(matches!(PoolId::for_mints(&jlp, &sol), Some(PoolId::SOL_JLP)));
+        assert!(PoolId::for_mints(&sol, &TokenId::USDC.to_string()).is_none());
+    }
+
     #[test]
     fn test_get_logo_by_mint_address() {
         let logo = get_token_logo_url_by_mint_address(&TokenId::USDC.to_string());
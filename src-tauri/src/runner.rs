@@ -2,111 +2,181 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 
+use futures::future::join_all;
 use tokio::sync::watch;
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
 
-use crate::feeder::PriceInfo;
-use crate::jup::{fetch_pair_price, fetch_price};
-use crate::token_registry::Token;
+use crate::feeder::{PairOrTokenAddress, PriceInfo};
+use crate::jup::TokenSymbol;
+use crate::price_source::PriceSourceRegistry;
+use crate::token_registry::{Token, TokenRegistry};
 
 const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A single entry in the watchlist: either one token or a tradeable pair.
+#[derive(Debug, Clone)]
+pub enum WatchTarget {
+    Single(Token),
+    Pair(Token, Token),
+}
+
+impl WatchTarget {
+    pub fn key(&self) -> PairOrTokenAddress {
+        match self {
+            WatchTarget::Single(token) => token.address.clone(),
+            WatchTarget::Pair(a, b) => format!("{}_{}", a.address, b.address),
+        }
+    }
+
+    async fn fetch(&self, sources: &PriceSourceRegistry) -> Result<f64> {
+        match self {
+            WatchTarget::Single(token) => sources.fetch_price(&token.address).await,
+            WatchTarget::Pair(a, b) => sources.fetch_pair_price(&a.address, &b.address).await,
+        }
+    }
+
+    /// Resolves a `price_watches` entry (e.g. `"SOL"` or `"JLP_SOL"`) to the
+    /// token(s) it refers to.
+    pub fn resolve(registry: &TokenRegistry, symbol: &str) -> Option<Self> {
+        let mut parts = symbol.split('_');
+        let first: TokenSymbol = parts.next()?.parse().ok()?;
+
+        match parts.next() {
+            Some(second) => {
+                let second: TokenSymbol = second.parse().ok()?;
+                let a = registry.get_by_symbol(&first)?.clone();
+                let b = registry.get_by_symbol(&second)?.clone();
+                Some(WatchTarget::Pair(a, b))
+            }
+            None => Some(WatchTarget::Single(registry.get_by_symbol(&first)?.clone())),
+        }
+    }
+}
+
+/// Resolves every `price_watches` entry to a `WatchTarget`, along with a
+/// `WatchTarget::key() -> symbol` map. `run_loop` only knows mint addresses,
+/// but `price_targets` are keyed by the human symbol, so this map is what
+/// lets an incoming price be matched back to the target that watches it.
+pub fn resolve_watch_targets(
+    registry: &TokenRegistry,
+    symbols: &[String],
+) -> (Vec<WatchTarget>, HashMap<PairOrTokenAddress, String>) {
+    let mut targets = Vec::new();
+    let mut key_to_symbol = HashMap::new();
+
+    for symbol in symbols {
+        if let Some(target) = WatchTarget::resolve(registry, symbol) {
+            key_to_symbol.insert(target.key(), symbol.clone());
+            targets.push(target);
+        }
+    }
+
+    (targets, key_to_symbol)
+}
+
+/// Per-asset retry/backoff bookkeeping so one failing feed doesn't stall the others.
+struct PollState {
+    retry_count: i32,
+    next_poll_at: Instant,
+}
+
+impl Default for PollState {
+    fn default() -> Self {
+        Self {
+            retry_count: 0,
+            next_poll_at: Instant::now(),
+        }
+    }
+}
+
+/// Exponent past which `MAX_BACKOFF` already clamps the result, so capping
+/// here just keeps `2f32.powi` from overflowing to infinity (and
+/// `Duration::mul_f32` from panicking on it) for a feed that keeps failing.
+const MAX_BACKOFF_EXPONENT: i32 = 10;
+
+fn backoff_for(retry_count: i32) -> Duration {
+    let exponent = (retry_count - 1).clamp(0, MAX_BACKOFF_EXPONENT);
+    Duration::from_secs(30).mul_f32(2f32.powi(exponent)).min(MAX_BACKOFF)
+}
 
 pub async fn run_loop(
-    price_sender: watch::Sender<HashMap<String, PriceInfo>>,
-    token_receiver: watch::Receiver<Vec<Token>>,
+    price_sender: watch::Sender<HashMap<PairOrTokenAddress, PriceInfo>>,
+    watch_receiver: watch::Receiver<Vec<WatchTarget>>,
 ) -> Result<()> {
-    let mut tokens = token_receiver.borrow().clone();
-    let mut retry_count = 0;
+    let sources = PriceSourceRegistry::default_order();
+    let mut targets = watch_receiver.borrow().clone();
+    let mut poll_states: HashMap<PairOrTokenAddress, PollState> = targets
+        .iter()
+        .map(|target| (target.key(), PollState::default()))
+        .collect();
 
     loop {
-        // Check for token changes
-        if token_receiver.has_changed()? {
-            tokens = token_receiver.borrow().clone();
-            retry_count = 0; // Reset retry counter on token change
-        }
+        if watch_receiver.has_changed()? {
+            targets = watch_receiver.borrow().clone();
 
-        // Fetch price with retry logic
-        let is_pair = tokens.len() == 2;
-
-        println!(
-            "Price fetch: {}",
-            tokens
-                .iter()
-                .map(|e| e.symbol.to_string())
-                .collect::<Vec<String>>()
-                .join("_")
-        );
-
-        if !is_pair {
-            let mut price_map = HashMap::new();
-            let address = tokens[0].address.clone();
-            match fetch_price(&address).await {
-                Ok(price) => {
-                    retry_count = 0; // Reset retry counter on success
-                    price_map.insert(
-                        address,
-                        PriceInfo {
-                            price: Some(price),
-                            retry_count,
-                        },
-                    );
-                    price_sender.send(price_map)?;
-                }
-                Err(e) => {
-                    retry_count += 1;
-                    println!("Price fetch failed (attempt {}): {}", retry_count, e);
-                    price_map.insert(
-                        address,
-                        PriceInfo {
-                            price: None,
-                            retry_count,
-                        },
-                    );
-                    price_sender.send(price_map)?;
-
-                    // Exponential backoff up to 5 minutes
-                    let backoff = Duration::from_secs(30).mul_f32(2f32.powi(retry_count - 1));
-                    sleep(backoff.min(Duration::from_secs(300))).await;
-                    continue;
-                }
+            // Keep poll state for assets that are still watched, reset anything new.
+            let keys: Vec<PairOrTokenAddress> = targets.iter().map(WatchTarget::key).collect();
+            poll_states.retain(|key, _| keys.contains(key));
+            for key in keys {
+                poll_states.entry(key).or_default();
             }
-        } else {
-            let address = format!("{}_{}", tokens[0].address, tokens[1].address);
-            let mut price_map = HashMap::new();
-
-            match fetch_pair_price(&tokens[0].address, &tokens[1].address).await {
-                Ok(price) => {
-                    retry_count = 0; // Reset retry counter on success
-                    price_map.insert(
-                        address,
-                        PriceInfo {
-                            price: Some(price),
-                            retry_count,
-                        },
-                    );
-                    price_sender.send(price_map)?;
-                }
-                Err(e) => {
-                    retry_count += 1;
-                    println!("Price fetch failed (attempt {}): {}", retry_count, e);
-                    price_map.insert(
-                        address,
-                        PriceInfo {
-                            price: None,
-                            retry_count,
-                        },
-                    );
-                    price_sender.send(price_map)?;
-
-                    // Exponential backoff up to 5 minutes
-                    let backoff = Duration::from_secs(30).mul_f32(2f32.powi(retry_count - 1));
-                    sleep(backoff.min(Duration::from_secs(300))).await;
-                    continue;
+        }
+
+        let due: Vec<&WatchTarget> = targets
+            .iter()
+            .filter(|target| {
+                poll_states
+                    .get(&target.key())
+                    .map(|state| Instant::now() >= state.next_poll_at)
+                    .unwrap_or(true)
+            })
+            .collect();
+
+        let fetches = due.iter().map(|target| async {
+            let key = target.key();
+            let result = target.fetch(&sources).await;
+            (key, result)
+        });
+
+        let results = join_all(fetches).await;
+
+        if !results.is_empty() {
+            let mut price_map = price_sender.borrow().clone();
+
+            for (key, result) in results {
+                let state = poll_states.entry(key.clone()).or_default();
+
+                match result {
+                    Ok(price) => {
+                        state.retry_count = 0;
+                        state.next_poll_at = Instant::now() + POLL_INTERVAL;
+                        price_map.insert(
+                            key,
+                            PriceInfo {
+                                price: Some(price),
+                                retry_count: state.retry_count,
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        state.retry_count += 1;
+                        println!("Price fetch failed for {} (attempt {}): {}", key, state.retry_count, e);
+                        state.next_poll_at = Instant::now() + backoff_for(state.retry_count);
+                        price_map.insert(
+                            key,
+                            PriceInfo {
+                                price: None,
+                                retry_count: state.retry_count,
+                            },
+                        );
+                    }
                 }
             }
-        };
 
-        // Wait for next poll
-        sleep(POLL_INTERVAL).await;
+            price_sender.send(price_map)?;
+        }
+
+        sleep(Duration::from_millis(500)).await;
     }
 }